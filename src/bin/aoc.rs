@@ -8,13 +8,17 @@ use anyhow::{ensure, Result};
 use clap::{value_parser, Parser, Subcommand};
 use tracing::{error, info};
 
-use libaoc::{Client, PuzzleId};
+use libaoc::{run, Client, PuzzleId};
 
 #[derive(Parser)]
 #[command(version, author, propagate_version = true)]
 struct Args {
     #[arg(long, short)]
     pub verbose: bool,
+    /// Serve puzzles and inputs only from the cache, never downloading them.
+    /// Submitting, `stats`, `board` and `whoami` still use the network.
+    #[arg(long)]
+    pub offline: bool,
     #[command(subcommand)]
     command: Command,
 }
@@ -54,13 +58,60 @@ enum Command {
         #[arg(long, short)]
         answers: bool,
     },
+    /// Run the solution against the cached examples and report pass/fail.
+    Test {
+        #[command(flatten)]
+        id: YearDay,
+        /// Shell command template, `{year}`/`{day}`/`{part}` get substituted.
+        #[arg(long)]
+        cmd: Option<String>,
+    },
+    /// Run the solution against the real input and optionally submit the result.
+    Run {
+        #[command(flatten)]
+        id: YearDay,
+        #[arg(long, short, value_parser = value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Shell command template, `{year}`/`{day}`/`{part}` get substituted.
+        #[arg(long)]
+        cmd: Option<String>,
+        /// Submit the solution's output as the answer.
+        #[arg(long, short)]
+        submit: bool,
+    },
+    /// Show a year's star progress as a 25-day grid.
+    Stats {
+        #[arg(long, short, value_parser = value_parser!(u16).range(2015..=2024))]
+        year: u16,
+    },
+    /// Show a private leaderboard, sorted by local score.
+    Board {
+        #[arg(long, short, value_parser = value_parser!(u16).range(2015..=2024))]
+        year: u16,
+        /// The leaderboard's id, from its URL.
+        id: u64,
+    },
+    /// Save a session token to config.toml for future runs.
+    Login {
+        /// The `session` cookie value from a logged-in browser.
+        token: String,
+    },
+    /// Print the username the current session token authenticates as.
+    Whoami,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     setup_logging(args.verbose)?;
 
-    let client = Client::new()?;
+    if let Command::Login { token } = &args.command {
+        Client::login(token.clone())?;
+        info!("session token saved");
+        return Ok(());
+    }
+
+    let mut client = Client::new()?;
+    client.set_offline(args.offline);
     let cwd = env::current_dir()?;
 
     match args.command {
@@ -88,6 +139,69 @@ fn main() -> Result<()> {
             let puzzle = client.get_puzzle(&id)?;
             println!("{}", puzzle.view(answers));
         }
+        Command::Test { id, cmd } => {
+            let id = derive_id(id, &cwd)?;
+            let puzzle = client.get_puzzle(&id)?;
+            let cmd = cmd.unwrap_or_else(|| client.config().run_command.clone());
+            let results = run::run_tests(&cmd, &id, &puzzle.tests)?;
+            let mut failed = 0;
+            let mut skipped = 0;
+            for (i, r) in results.iter().enumerate() {
+                if r.expected.is_none() {
+                    skipped += 1;
+                    println!(
+                        "test {i} (part {}) ... skipped, no expected output ({:?})",
+                        r.part, r.elapsed
+                    );
+                } else if r.passed() {
+                    println!("test {i} (part {}) ... ok ({:?})", r.part, r.elapsed);
+                } else {
+                    failed += 1;
+                    println!(
+                        "test {i} (part {}) ... FAILED ({:?})\n  expected: {:?}\n  actual:   {actual:?}",
+                        r.part,
+                        r.elapsed,
+                        r.expected,
+                        actual = r.actual,
+                    );
+                }
+            }
+            println!(
+                "{} passed, {failed} failed, {skipped} skipped",
+                results.len() - failed - skipped
+            );
+        }
+        Command::Run {
+            id,
+            part,
+            cmd,
+            submit,
+        } => {
+            let id = derive_id(id, &cwd)?;
+            let input = client.get_input(&id)?;
+            let cmd = cmd.unwrap_or_else(|| client.config().run_command.clone());
+            let part = part.unwrap_or(1);
+            let (answer, elapsed) = run::run_solution(&cmd, &id, part, &input)?;
+            println!("{answer} ({elapsed:?})");
+            if submit {
+                if let Some(puzzle) = client.submit(&id, Some(part), &answer)? {
+                    puzzle.write_view(cwd.join("puzzle.md"))?;
+                }
+            }
+        }
+        Command::Stats { year } => {
+            let stars = client.get_stars(year)?;
+            println!("{year}\n{}", libaoc::leaderboard::render_grid(&stars));
+        }
+        Command::Board { year, id } => {
+            let board = client.get_leaderboard(year, id)?;
+            println!("{}", libaoc::leaderboard::render_table(&board));
+        }
+        Command::Login { .. } => unreachable!("handled before the client is built"),
+        Command::Whoami => match client.whoami()? {
+            Some(name) => println!("{name}"),
+            None => error!("session token is missing or expired"),
+        },
     }
 
     Ok(())
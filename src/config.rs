@@ -0,0 +1,100 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{home_dir, run::DEFAULT_RUN_CMD, AUTH_VAR, CACHE_PATH};
+
+/// Current on-disk schema version. Bump this and extend [`migrate`] whenever
+/// the config layout changes.
+const CONFIG_VERSION: &str = "1";
+
+/// User configuration, loaded from `~/.config/libaoc/config.toml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub version: String,
+    #[serde(default = "default_data_dir")]
+    pub data_dir: PathBuf,
+    /// Falls back to the `AOC_AUTH_TOKEN` environment variable when unset.
+    #[serde(default)]
+    pub session_token: Option<String>,
+    #[serde(default = "default_wrap_width")]
+    pub wrap_width: usize,
+    #[serde(default = "default_run_command")]
+    pub run_command: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION.into(),
+            data_dir: default_data_dir(),
+            session_token: None,
+            wrap_width: default_wrap_width(),
+            run_command: default_run_command(),
+        }
+    }
+}
+
+fn default_data_dir() -> PathBuf {
+    home_dir().join(CACHE_PATH)
+}
+
+fn default_wrap_width() -> usize {
+    80
+}
+
+fn default_run_command() -> String {
+    DEFAULT_RUN_CMD.into()
+}
+
+impl Config {
+    /// Load the config from disk, migrating it in place if its `version` is
+    /// missing or older than [`CONFIG_VERSION`].
+    pub fn load() -> Result<Self> {
+        let path = config_path()?;
+        let mut table: toml::Value = if path.exists() {
+            toml::from_str(&fs::read_to_string(&path).context("read config")?)
+                .context("parse config")?
+        } else {
+            toml::Value::Table(Default::default())
+        };
+
+        let version = table
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("0")
+            .to_string();
+
+        if version != CONFIG_VERSION {
+            migrate(table.as_table_mut().context("config must be a table")?, &version);
+            fs::create_dir_all(path.parent().unwrap()).context("mkdir config dir")?;
+            fs::write(&path, toml::to_string_pretty(&table)?).context("write config")?;
+        }
+
+        let mut config: Config = table.try_into().context("invalid config")?;
+        if config.session_token.is_none() {
+            config.session_token = env::var(AUTH_VAR).ok();
+        }
+        Ok(config)
+    }
+
+    /// Write the config back to `~/.config/libaoc/config.toml`.
+    pub fn save(&self) -> Result<()> {
+        let path = config_path()?;
+        fs::create_dir_all(path.parent().unwrap()).context("mkdir config dir")?;
+        fs::write(&path, toml::to_string_pretty(self)?).context("write config")
+    }
+}
+
+/// Rewrite `table` to the latest schema, preserving any unknown keys.
+fn migrate(table: &mut toml::value::Table, from: &str) {
+    // No prior schema versions exist yet; missing fields are simply backfilled
+    // with their defaults by `Config`'s `#[serde(default)]`s.
+    let _ = from;
+    table.insert("version".into(), CONFIG_VERSION.into());
+}
+
+fn config_path() -> Result<PathBuf> {
+    Ok(home_dir().join(".config/libaoc/config.toml"))
+}
@@ -0,0 +1,113 @@
+use std::{collections::HashMap, fmt::Write};
+
+use anyhow::Result;
+use scraper::{Html, Selector};
+use serde::Deserialize;
+
+use crate::{Client, AOC_URL};
+
+/// A private leaderboard, as returned by AoC's own JSON endpoint.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u32,
+    pub last_star_ts: u64,
+    #[serde(default)]
+    pub completion_day_level: HashMap<String, HashMap<String, DayLevel>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DayLevel {
+    pub get_star_ts: u64,
+}
+
+impl Client {
+    /// Fetch a private leaderboard.
+    pub fn get_leaderboard(&self, year: u16, board_id: u64) -> Result<Leaderboard> {
+        let url = format!("{AOC_URL}/{year}/leaderboard/private/view/{board_id}.json");
+        let board = self.http.get(url).send()?.error_for_status()?.json()?;
+        Ok(board)
+    }
+
+    /// Scrape the personal calendar page to see which days/parts are complete.
+    /// Index `0` is day 1, each entry is `0` (nothing), `1` (part one) or `2`
+    /// (both parts).
+    pub fn get_stars(&self, year: u16) -> Result<[u8; 25]> {
+        let html = self
+            .http
+            .get(format!("{AOC_URL}/{year}"))
+            .send()?
+            .error_for_status()?
+            .text()?;
+
+        let doc = Html::parse_document(&html);
+        let query = Selector::parse("pre.calendar a").unwrap();
+        let mut stars = [0u8; 25];
+        for el in doc.select(&query) {
+            let Some(day) = el
+                .value()
+                .attr("href")
+                .and_then(|href| href.rsplit('/').next())
+                .and_then(|d| d.parse::<usize>().ok())
+                .filter(|d| (1..=25).contains(d))
+            else {
+                continue;
+            };
+            let class = el.value().attr("class").unwrap_or_default();
+            stars[day - 1] = if class.contains("calendar-verycomplete") {
+                2
+            } else if class.contains("calendar-complete") {
+                1
+            } else {
+                0
+            };
+        }
+        Ok(stars)
+    }
+}
+
+/// Render a year's stars as a 25-day grid of `*`/`**`/`.`.
+pub fn render_grid(stars: &[u8; 25]) -> String {
+    let mut buf = String::new();
+    for (i, s) in stars.iter().enumerate() {
+        let glyph = match s {
+            2 => "**",
+            1 => "* ",
+            _ => ". ",
+        };
+        let _ = write!(&mut buf, "{:>2} {glyph} ", i + 1);
+        if (i + 1) % 5 == 0 {
+            buf.push('\n');
+        }
+    }
+    buf
+}
+
+/// Render a leaderboard as a table sorted by local score, highest first.
+pub fn render_table(board: &Leaderboard) -> String {
+    let mut members: Vec<&Member> = board.members.values().collect();
+    members.sort_by_key(|m| std::cmp::Reverse(m.local_score));
+
+    let mut buf = String::new();
+    for (rank, m) in members.iter().enumerate() {
+        let name = m.name.as_deref().unwrap_or("(anonymous user)");
+        let _ = writeln!(
+            &mut buf,
+            "{:>3}. {:<25} {:>5} pts  {:>2} stars",
+            rank + 1,
+            name,
+            m.local_score,
+            m.stars
+        );
+    }
+    buf
+}
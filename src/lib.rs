@@ -1,15 +1,26 @@
 use std::{
-    env,
-    fmt::Write,
-    fs,
+    cmp::Ordering,
+    env, fs,
+    io::{self, Write},
     path::{Path, PathBuf},
-    process,
+    thread,
+    time::Duration,
 };
 
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use reqwest::{header::HeaderMap, redirect::Policy};
 use scraper::{Html, Selector};
-use tracing::{error, warn};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+mod config;
+pub use config::Config;
+
+mod puzzle;
+pub use puzzle::{Puzzle, TestCase};
+
+pub mod leaderboard;
+pub mod run;
 
 pub const AOC_URL: &str = "https://adventofcode.com";
 pub const AUTH_VAR: &str = "AOC_AUTH_TOKEN";
@@ -20,16 +31,19 @@ pub type PuzzleId = (u16, u8);
 
 /// The `Advent of Code` client handles puzzle retrieval and cache.
 pub struct Client {
-    http: reqwest::blocking::Client,
+    pub(crate) http: reqwest::blocking::Client,
     cache: Cache,
+    config: Config,
+    offline: bool,
 }
 
 impl Client {
     pub fn new() -> Result<Self> {
-        let token = env::var(AUTH_VAR).unwrap_or_else(|e| {
-            error!(cause = %e, AUTH_VAR);
-            process::exit(1);
-        });
+        let config = Config::load()?;
+        let token = config
+            .session_token
+            .clone()
+            .context("no session token: set `session_token` in config.toml or AOC_AUTH_TOKEN")?;
 
         let mut headers = HeaderMap::new();
         headers.insert("cookie", format!("session={token}").parse()?);
@@ -39,15 +53,52 @@ impl Client {
                 .default_headers(headers)
                 .redirect(Policy::none())
                 .build()?,
-            cache: Cache::new(home_dir().join(CACHE_PATH))?,
+            cache: Cache::new(&config.data_dir)?,
+            config,
+            offline: false,
         })
     }
 
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// When set, `get_puzzle` and `get_input` serve only what is already
+    /// cached and fail cleanly otherwise. Submitting, `whoami`, `get_stars`,
+    /// `get_leaderboard` and the `download_*`/`scrape_*` methods still go to
+    /// the network.
+    pub fn set_offline(&mut self, offline: bool) -> &mut Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Persist a session token to `config.toml` so future `Client::new` calls
+    /// pick it up without needing `AOC_AUTH_TOKEN` set.
+    pub fn login(token: impl Into<String>) -> Result<()> {
+        let mut config = Config::load()?;
+        config.session_token = Some(token.into());
+        config.save()
+    }
+
+    /// Scrape the logged-in username off the front page. `None` means the
+    /// session cookie is missing, expired, or otherwise not authenticating us.
+    pub fn whoami(&self) -> Result<Option<String>> {
+        let html = self.http.get(AOC_URL).send()?.error_for_status()?.text()?;
+        let doc = Html::parse_document(&html);
+        let query = Selector::parse(".user").unwrap();
+        Ok(doc.select(&query).next().map(|el| {
+            // `.user`'s first text node is the username; any further text
+            // (e.g. a nested `.star-count` span) is not part of the name.
+            el.text().next().unwrap_or_default().trim().to_string()
+        }))
+    }
+
     /// Get a puzzle from cache or by scraping the website if not found.
     pub fn get_puzzle(&self, id: &PuzzleId) -> Result<Puzzle> {
         if let Some(puzzle) = self.cache.get(id) {
             return Ok(puzzle);
         }
+        ensure!(!self.offline, "puzzle {id:?} is not cached and offline mode is set");
         self.download_puzzle(id)
     }
 
@@ -63,6 +114,7 @@ impl Client {
         if let Some(input) = self.cache.get_input(id) {
             return Ok(input);
         }
+        ensure!(!self.offline, "input for {id:?} is not cached and offline mode is set");
         self.download_input(id)
     }
 
@@ -87,15 +139,16 @@ impl Client {
             .error_for_status()?
             .text()?;
 
+        let wrap_width = self.config.wrap_width;
         let doc = Html::parse_document(&html);
         let query = Selector::parse("article.day-desc").unwrap();
         let mut questions = doc.select(&query);
         let q1 = questions
             .next()
-            .and_then(|el| html2text::from_read(el.inner_html().as_bytes(), 80).ok());
+            .and_then(|el| html2text::from_read(el.inner_html().as_bytes(), wrap_width).ok());
         let q2 = questions
             .next()
-            .and_then(|el| html2text::from_read(el.inner_html().as_bytes(), 80).ok());
+            .and_then(|el| html2text::from_read(el.inner_html().as_bytes(), wrap_width).ok());
 
         let query = Selector::parse("article.day-desc + p code").unwrap();
         let mut answers = doc.select(&query);
@@ -108,70 +161,227 @@ impl Client {
             q2,
             a1,
             a2,
+            tests: Client::extract_tests(&html),
         })
     }
 
     /// Submit a puzzle's answer for a specific part.
+    ///
+    /// Refuses locally (without POSTing) when `answer` is already known to be
+    /// wrong, or falls outside the high/low bounds narrowed by earlier hints.
     pub fn submit(
         &self,
         id: &PuzzleId,
         part: Option<u8>,
         answer: impl AsRef<str>,
     ) -> Result<Option<Puzzle>> {
-        // TODO: Check for answers in cache to be able to submit once the puzzle
-        // is finished.
-        let path = self.cache.mkpath(id);
-        let part = part.unwrap_or_else(|| {
+        let part = self.resolve_part(id, part);
+        let answer = answer.as_ref();
+
+        if let Some(reason) = self.cache.known_wrong(id, part, answer) {
+            println!("Refusing to submit `{answer}`: {reason}");
+            return Ok(None);
+        }
+
+        match self.submit_once(id, part, answer)? {
+            Submit::Correct => {
+                println!("Correct!");
+                Ok(Some(self.download_puzzle(id)?))
+            }
+            Submit::Incorrect { message, hint, wait } => {
+                println!("{message}{}", hint_suffix(hint));
+                if let Some(remaining) = wait {
+                    println!("(and now on cooldown for {remaining:?})");
+                }
+                self.cache.record_wrong(id, part, answer, hint);
+                Ok(None)
+            }
+            Submit::Wait { remaining } => {
+                println!("Wait! ({remaining:?} left)");
+                Ok(None)
+            }
+            Submit::Error => {
+                println!("Unknown response");
+                Ok(None)
+            }
+        }
+    }
+
+    /// Like [`Client::submit`], but sleeps out any rate-limit cooldown (with a
+    /// live countdown) and retries automatically, up to `max_retries` times.
+    pub fn submit_blocking(
+        &self,
+        id: &PuzzleId,
+        part: Option<u8>,
+        answer: impl AsRef<str>,
+        max_retries: u32,
+    ) -> Result<Option<Puzzle>> {
+        let part = self.resolve_part(id, part);
+        let answer = answer.as_ref();
+
+        if let Some(reason) = self.cache.known_wrong(id, part, answer) {
+            println!("Refusing to submit `{answer}`: {reason}");
+            return Ok(None);
+        }
+
+        for attempt in 0..=max_retries {
+            match self.submit_once(id, part, answer)? {
+                Submit::Correct => {
+                    println!("Correct!");
+                    return Ok(Some(self.download_puzzle(id)?));
+                }
+                Submit::Incorrect { message, hint, wait } => {
+                    println!("{message}{}", hint_suffix(hint));
+                    if let Some(remaining) = wait {
+                        println!("(and now on cooldown for {remaining:?})");
+                    }
+                    self.cache.record_wrong(id, part, answer, hint);
+                    return Ok(None);
+                }
+                Submit::Wait { remaining } if attempt < max_retries => {
+                    wait_with_countdown(remaining);
+                }
+                Submit::Wait { .. } => {
+                    println!("still rate-limited after {max_retries} retries, giving up");
+                    return Ok(None);
+                }
+                Submit::Error => {
+                    println!("Unknown response");
+                    return Ok(None);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn resolve_part(&self, id: &PuzzleId, part: Option<u8>) -> u8 {
+        part.unwrap_or_else(|| {
+            let path = self.cache.mkpath(id);
             if fs::metadata(path.join("a1")).is_ok_and(|m| m.len() > 0) {
                 2
             } else {
                 1
             }
-        });
+        })
+    }
 
+    fn submit_once(&self, id: &PuzzleId, part: u8, answer: &str) -> Result<Submit> {
         let html = self
             .http
             .post(format!("{}/answer", self.mkurl(id)))
             .header("content-type", "application/x-www-form-urlencoded")
-            .body(format!("level={}&answer={}", part, answer.as_ref()))
+            .body(format!("level={part}&answer={answer}"))
             .send()?
             .error_for_status()?
             .text()?;
-
-        match self.submission_outcome(&html) {
-            Submit::Correct => {
-                println!("Correct!");
-                return Ok(Some(self.download_puzzle(id)?));
-            }
-            Submit::Incorrect => println!("Incorrect!"),
-            Submit::Wait => println!("Wait!"),
-            Submit::Error => println!("Unknown response"),
-        };
-        Ok(None)
+        Ok(self.submission_outcome(&html))
     }
 
     fn submission_outcome(&self, response: &str) -> Submit {
         if response.contains("That's the right answer") {
             Submit::Correct
         } else if response.contains("That's not the right answer") {
-            Submit::Incorrect
+            let hint = if response.contains("too high") {
+                Some(Ordering::Greater)
+            } else if response.contains("too low") {
+                Some(Ordering::Less)
+            } else {
+                None
+            };
+            Submit::Incorrect {
+                message: extract_message(response, "That's not the right answer"),
+                hint,
+                wait: parse_wait_seconds(response).map(Duration::from_secs),
+            }
         } else if response.contains("You gave an answer too recently") {
-            Submit::Wait
+            Submit::Wait {
+                remaining: Duration::from_secs(parse_wait_seconds(response).unwrap_or(60)),
+            }
         } else {
             Submit::Error
         }
     }
 
-    fn mkurl(&self, (y, d): &PuzzleId) -> String {
+    pub(crate) fn mkurl(&self, (y, d): &PuzzleId) -> String {
         format!("{AOC_URL}/{y}/day/{d}")
     }
 }
 
+fn hint_suffix(hint: Option<Ordering>) -> &'static str {
+    match hint {
+        Some(Ordering::Greater) => " (too high)",
+        Some(Ordering::Less) => " (too low)",
+        Some(Ordering::Equal) | None => "",
+    }
+}
+
+/// Sleep out `remaining`, printing a live countdown.
+fn wait_with_countdown(remaining: Duration) {
+    let mut left = remaining.as_secs();
+    loop {
+        print!("\rwaiting {left}s...  ");
+        let _ = io::stdout().flush();
+        if left == 0 {
+            break;
+        }
+        thread::sleep(Duration::from_secs(1));
+        left -= 1;
+    }
+    println!();
+}
+
+/// Extract the sentence starting at `marker` out of a response, trimmed to
+/// its first full stop or line break.
+fn extract_message(response: &str, marker: &str) -> String {
+    response
+        .find(marker)
+        .map(|start| {
+            response[start..]
+                .split(['.', '\n'])
+                .next()
+                .unwrap_or(&response[start..])
+                .trim()
+                .to_string()
+        })
+        .unwrap_or_else(|| response.trim().to_string())
+}
+
+/// Pull the remaining cooldown out of a "you have Xm Ys left to wait" (or
+/// "N minutes left to wait") response.
+fn parse_wait_seconds(text: &str) -> Option<u64> {
+    let before = &text[..text.find("left to wait")?];
+    let window = before.rsplit(['.', ';']).next()?;
+
+    let mut minutes = 0u64;
+    let mut seconds = 0u64;
+    let mut saw_unit = false;
+    for tok in window.split_whitespace() {
+        if let Some(n) = tok.strip_suffix('m').and_then(|n| n.parse().ok()) {
+            minutes = n;
+            saw_unit = true;
+        } else if let Some(n) = tok.strip_suffix('s').and_then(|n| n.parse().ok()) {
+            seconds = n;
+            saw_unit = true;
+        } else if let Ok(n) = tok.parse::<u64>() {
+            // "N minutes left to wait" with no unit suffix on the number.
+            minutes = n;
+            saw_unit = true;
+        }
+    }
+    saw_unit.then_some(minutes * 60 + seconds)
+}
+
 /// The outcome of a puzzle submission.
 pub enum Submit {
     Correct,
-    Incorrect,
-    Wait,
+    Incorrect {
+        message: String,
+        hint: Option<Ordering>,
+        wait: Option<Duration>,
+    },
+    Wait {
+        remaining: Duration,
+    },
     Error,
 }
 
@@ -225,81 +435,97 @@ impl Cache {
             .unwrap_or_else(|_| warn!("failed to update answer"));
     }
 
-    fn mkpath(&self, (y, d): &PuzzleId) -> PathBuf {
-        self.path.join(format!("{y}/{d}"))
-    }
-}
-
-#[derive(Debug, Default, Clone)]
-pub struct Puzzle {
-    pub id: PuzzleId,
-    pub q1: Option<String>,
-    pub q2: Option<String>,
-    pub a1: Option<String>,
-    pub a2: Option<String>,
-}
-
-impl Puzzle {
-    pub fn read(path: impl AsRef<Path>, id: &PuzzleId) -> Puzzle {
-        let path = path.as_ref();
-        Puzzle {
-            id: *id,
-            q1: fs::read_to_string(path.join("q1")).ok(),
-            q2: fs::read_to_string(path.join("q2")).ok(),
-            a1: fs::read_to_string(path.join("a1")).ok(),
-            a2: fs::read_to_string(path.join("a2")).ok(),
+    /// Record an answer that came back incorrect, along with its high/low hint.
+    pub fn record_wrong(&self, id: &PuzzleId, part: u8, answer: &str, hint: Option<Ordering>) {
+        let mut wrong = self.wrong(id);
+        wrong.push(WrongAnswer {
+            part,
+            answer: answer.into(),
+            hint,
+        });
+        let dir = self.mkpath(id);
+        if let Err(e) = fs::create_dir_all(&dir) {
+            warn!("failed to create cache dir {}: {e}", dir.display());
+            return;
+        }
+        match serde_json::to_string(&wrong) {
+            Ok(data) => fs::write(dir.join("wrong.json"), data)
+                .unwrap_or_else(|e| warn!("failed to write wrong.json: {e}")),
+            Err(e) => warn!("failed to serialize wrong.json: {e}"),
         }
     }
 
-    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
-        let path = path.as_ref();
-        fs::create_dir_all(path)?;
-        if let Some(q) = &self.q1 {
-            fs::write(path.join("q1"), q.as_bytes())?;
-        }
-        if let Some(q) = &self.q2 {
-            fs::write(path.join("q2"), q.as_bytes())?;
-        }
-        if let Some(a) = &self.a1 {
-            fs::write(path.join("a1"), a.as_bytes())?;
-        }
-        if let Some(a) = &self.a2 {
-            fs::write(path.join("a2"), a.as_bytes())?;
-        }
-        Ok(())
+    fn wrong(&self, id: &PuzzleId) -> Vec<WrongAnswer> {
+        fs::read_to_string(self.mkpath(id).join("wrong.json"))
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
     }
 
-    pub fn view(&self, show_answers: bool) -> String {
-        let mut buf = String::new();
-        if let Some(q1) = &self.q1 {
-            let _ = writeln!(&mut buf, "{q1}");
-            if show_answers {
-                if let Some(a1) = &self.a1 {
-                    let _ = writeln!(&mut buf, "**Answer**: `{a1}`.");
-                }
+    /// If `answer` is already known to be wrong for `part` (either submitted
+    /// before, or outside the bounds narrowed by earlier high/low hints),
+    /// return a reason not to submit it again.
+    pub fn known_wrong(&self, id: &PuzzleId, part: u8, answer: &str) -> Option<String> {
+        let wrong = self.wrong(id);
+        let mut too_high: Option<i64> = None;
+        let mut too_low: Option<i64> = None;
+        for w in wrong.iter().filter(|w| w.part == part) {
+            if w.answer == answer {
+                return Some("already submitted and found incorrect".into());
             }
-        }
-        if let Some(q2) = &self.q2 {
-            let _ = writeln!(&mut buf, "\n{q2}");
-            if show_answers {
-                if let Some(a2) = &self.a2 {
-                    let _ = writeln!(&mut buf, "**Answer**: `{a2}`.");
+            if let Ok(n) = w.answer.parse::<i64>() {
+                match w.hint {
+                    Some(Ordering::Greater) => too_high = Some(too_high.map_or(n, |h| h.min(n))),
+                    Some(Ordering::Less) => too_low = Some(too_low.map_or(n, |l| l.max(n))),
+                    _ => {}
                 }
             }
         }
-        buf
+        let n = answer.parse::<i64>().ok()?;
+        if too_high.is_some_and(|h| n >= h) {
+            return Some(format!("`{n}` is >= a previously too-high answer"));
+        }
+        if too_low.is_some_and(|l| n <= l) {
+            return Some(format!("`{n}` is <= a previously too-low answer"));
+        }
+        None
     }
 
-    pub fn write_view(&self, path: impl AsRef<Path>) -> Result<()> {
-        Ok(fs::write(path, self.view(true))?)
+    fn mkpath(&self, (y, d): &PuzzleId) -> PathBuf {
+        self.path.join(format!("{y}/{d}"))
     }
 }
 
-fn home_dir() -> PathBuf {
-    PathBuf::from(env::var("HOME").unwrap_or_else(|e| {
-        error!(cause = %e, "HOME");
-        process::exit(1);
-    }))
+/// A previously submitted answer that AoC reported as incorrect.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WrongAnswer {
+    part: u8,
+    answer: String,
+    #[serde(with = "ordering_hint")]
+    hint: Option<Ordering>,
+}
+
+/// `serde` has no impl for `std::cmp::Ordering`, so store the hint as the
+/// `-1`/`0`/`1` its discriminant already is.
+mod ordering_hint {
+    use std::cmp::Ordering;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(hint: &Option<Ordering>, s: S) -> Result<S::Ok, S::Error> {
+        hint.map(|o| o as i8).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<Option<Ordering>, D::Error> {
+        Ok(Option::<i8>::deserialize(d)?.map(|n| n.cmp(&0)))
+    }
+}
+
+pub(crate) fn home_dir() -> PathBuf {
+    env::var("HOME").map(PathBuf::from).unwrap_or_else(|_| {
+        warn!("HOME not set, falling back to the current directory");
+        PathBuf::from(".")
+    })
 }
 
 /// Determine the puzzle's year and day from a path.
@@ -307,13 +533,10 @@ pub fn puzzle_id_from_path(path: impl AsRef<Path>) -> Option<PuzzleId> {
     let mut day = 0xff;
     let mut year = 0;
     for parent in path.as_ref().ancestors() {
-        let mut chars = parent
-            .file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .chars()
-            .peekable();
+        let Some(name) = parent.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let mut chars = name.chars().peekable();
         let mut buf = String::new();
         while let Some(c) = chars.next() {
             if c.is_ascii_digit() {
@@ -341,29 +564,6 @@ pub fn puzzle_id_from_path(path: impl AsRef<Path>) -> Option<PuzzleId> {
 mod tests {
     use super::*;
 
-    fn derive_id_from_path(path: impl AsRef<Path>) -> Result<(Option<u16>, Option<u8>)> {
-        for parent in path.as_ref().ancestors() {
-            let mut buf = String::new();
-            let mut chars = parent
-                .file_name()
-                .unwrap()
-                .to_str()
-                .unwrap()
-                .chars()
-                .peekable();
-
-            while let Some(c) = chars.next() {
-                if c.is_ascii_digit() {
-                    buf.push(c);
-                    if !chars.peek().is_some_and(|c| c.is_ascii_digit()) {
-                        break;
-                    }
-                }
-            }
-        }
-        todo!()
-    }
-
     #[test]
     fn from_path() {
         let cases = vec![
@@ -379,6 +579,36 @@ mod tests {
 
         assert_eq!(puzzle_id_from_path("/invalid/path"), None)
     }
+
+    #[test]
+    fn wait_seconds_parses_minutes_and_seconds() {
+        assert_eq!(
+            parse_wait_seconds("You have 1m 30s left to wait."),
+            Some(90)
+        );
+        assert_eq!(
+            parse_wait_seconds("Please wait 5 minutes left to wait."),
+            Some(300)
+        );
+        assert_eq!(parse_wait_seconds("no cooldown mentioned here"), None);
+    }
+
+    #[test]
+    fn known_wrong_rejects_resubmission_and_narrows_bounds() {
+        let dir = env::temp_dir().join(format!("libaoc-test-known-wrong-{}", std::process::id()));
+        let cache = Cache::new(&dir).unwrap();
+        let id = (2024, 1);
+
+        cache.record_wrong(&id, 1, "100", Some(Ordering::Greater));
+        cache.record_wrong(&id, 1, "10", Some(Ordering::Less));
+
+        assert!(cache.known_wrong(&id, 1, "100").is_some());
+        assert!(cache.known_wrong(&id, 1, "150").is_some());
+        assert!(cache.known_wrong(&id, 1, "5").is_some());
+        assert!(cache.known_wrong(&id, 1, "50").is_none());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
 
 // struct Id(u32, u32);
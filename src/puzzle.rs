@@ -1,35 +1,264 @@
-use std::{fs, io, path::Path};
+use std::{fmt::Write, fs, path::Path};
 
-pub type PuzzleId = (u32, u32);
+use anyhow::Result;
+use scraper::{ElementRef, Html, Selector};
 
-#[derive(Debug, Default)]
+use crate::{Client, PuzzleId};
+
+#[derive(Debug, Default, Clone)]
 pub struct Puzzle {
     pub id: PuzzleId,
-    pub q1: String,
-    pub q2: String,
-    pub a1: String,
-    pub a2: String,
+    pub q1: Option<String>,
+    pub q2: Option<String>,
+    pub a1: Option<String>,
+    pub a2: Option<String>,
+    pub tests: Vec<TestCase>,
+}
+
+/// A worked example embedded in a puzzle's description.
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TestCase {
+    pub input: String,
+    pub expected: Option<String>,
+    pub part: u8,
 }
 
 impl Puzzle {
-    pub fn read(path: impl AsRef<Path>, id: PuzzleId) -> Option<Puzzle> {
+    pub fn read(path: impl AsRef<Path>, id: &PuzzleId) -> Puzzle {
         let path = path.as_ref();
-        path.exists().then(|| Puzzle {
-            id,
-            q1: fs::read_to_string(path.join("question1")).unwrap_or_default(),
-            q2: fs::read_to_string(path.join("question2")).unwrap_or_default(),
-            a1: fs::read_to_string(path.join("answer1")).unwrap_or_default(),
-            a2: fs::read_to_string(path.join("answer2")).unwrap_or_default(),
-        })
+        Puzzle {
+            id: *id,
+            q1: fs::read_to_string(path.join("q1")).ok(),
+            q2: fs::read_to_string(path.join("q2")).ok(),
+            a1: fs::read_to_string(path.join("a1")).ok(),
+            a2: fs::read_to_string(path.join("a2")).ok(),
+            tests: fs::read_to_string(path.join("tests.json"))
+                .ok()
+                .and_then(|s| serde_json::from_str(&s).ok())
+                .unwrap_or_default(),
+        }
     }
 
-    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<()> {
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
         let path = path.as_ref();
         fs::create_dir_all(path)?;
-        fs::write(path.join("question1"), self.q1.as_bytes())?;
-        fs::write(path.join("question2"), self.q2.as_bytes())?;
-        fs::write(path.join("answer1"), self.a1.as_bytes())?;
-        fs::write(path.join("answer2"), self.a2.as_bytes())?;
+        if let Some(q) = &self.q1 {
+            fs::write(path.join("q1"), q.as_bytes())?;
+        }
+        if let Some(q) = &self.q2 {
+            fs::write(path.join("q2"), q.as_bytes())?;
+        }
+        if let Some(a) = &self.a1 {
+            fs::write(path.join("a1"), a.as_bytes())?;
+        }
+        if let Some(a) = &self.a2 {
+            fs::write(path.join("a2"), a.as_bytes())?;
+        }
+        if !self.tests.is_empty() {
+            fs::write(path.join("tests.json"), serde_json::to_string(&self.tests)?)?;
+        }
         Ok(())
     }
+
+    pub fn view(&self, show_answers: bool) -> String {
+        let mut buf = String::new();
+        if let Some(q1) = &self.q1 {
+            let _ = writeln!(&mut buf, "{q1}");
+            if show_answers {
+                if let Some(a1) = &self.a1 {
+                    let _ = writeln!(&mut buf, "**Answer**: `{a1}`.");
+                }
+            }
+        }
+        if let Some(q2) = &self.q2 {
+            let _ = writeln!(&mut buf, "\n{q2}");
+            if show_answers {
+                if let Some(a2) = &self.a2 {
+                    let _ = writeln!(&mut buf, "**Answer**: `{a2}`.");
+                }
+            }
+        }
+        buf
+    }
+
+    pub fn write_view(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(fs::write(path, self.view(true))?)
+    }
+
+    /// Test cases belonging to a specific part.
+    pub fn tests_for(&self, part: u8) -> impl Iterator<Item = &TestCase> {
+        self.tests.iter().filter(move |t| t.part == part)
+    }
+}
+
+impl Client {
+    /// Scrape the worked examples out of a puzzle's description articles.
+    ///
+    /// Walks each `article.day-desc` in document order: a multi-line
+    /// `<pre><code>` block becomes a pending sample input, and the inline
+    /// `<code>`/`<em>` tokens that follow (before the next `<pre>`) are
+    /// candidate answers. A token introduced by an answer phrase (e.g.
+    /// "...your puzzle answer was `42`") wins; otherwise the *last* plausible
+    /// token wins, since walkthroughs usually state the result only after
+    /// mentioning any intermediate values. When no candidate qualifies,
+    /// `expected` is left `None` rather than guessed.
+    pub fn scrape_tests(&self, id: &PuzzleId) -> Result<Vec<TestCase>> {
+        let html = self
+            .http
+            .get(self.mkurl(id))
+            .send()?
+            .error_for_status()?
+            .text()?;
+        Ok(Self::extract_tests(&html))
+    }
+
+    pub(crate) fn extract_tests(html: &str) -> Vec<TestCase> {
+        let doc = Html::parse_document(html);
+        let articles = Selector::parse("article.day-desc").unwrap();
+        let pre_code = Selector::parse("pre code").unwrap();
+        let inline_code = Selector::parse("code").unwrap();
+        let em = Selector::parse("em").unwrap();
+
+        let mut tests = Vec::new();
+        for (i, article) in doc.select(&articles).enumerate() {
+            let part = i as u8 + 1;
+            let mut pending: Option<String> = None;
+            let mut phrase_match: Option<String> = None;
+            let mut last_plausible: Option<String> = None;
+
+            for node in article.descendants() {
+                let Some(el) = ElementRef::wrap(node) else {
+                    continue;
+                };
+                if pre_code.matches(&el) {
+                    let text = el.text().collect::<String>();
+                    if text.lines().count() > 1 {
+                        if let Some(input) = pending.take() {
+                            tests.push(TestCase {
+                                input,
+                                expected: phrase_match.take().or(last_plausible.take()),
+                                part,
+                            });
+                        }
+                        pending = Some(text.trim_end().to_string());
+                    }
+                } else if (inline_code.matches(&el) || em.matches(&el)) && pending.is_some() {
+                    let text = el.text().collect::<String>();
+                    if nearby_answer_phrase(&el) {
+                        phrase_match.get_or_insert(text);
+                    } else if is_plausible_answer(&text) {
+                        last_plausible = Some(text);
+                    }
+                }
+            }
+
+            if let Some(input) = pending {
+                tests.push(TestCase {
+                    input,
+                    expected: phrase_match.or(last_plausible),
+                    part,
+                });
+            }
+        }
+        tests
+    }
+}
+
+/// A short token that looks like a puzzle answer (a number, or a handful of
+/// non-whitespace characters), as opposed to a snippet of code.
+fn is_plausible_answer(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty() && text.len() <= 20 && !text.contains(char::is_whitespace)
+}
+
+/// Phrases AoC statements typically use to introduce a worked example's
+/// result, e.g. "...your puzzle answer was `42`." or "...would produce `8`".
+const ANSWER_PHRASES: [&str; 4] = [
+    "your puzzle answer was",
+    "would produce",
+    "produces",
+    "would be",
+];
+
+/// Whether `el` is preceded, among its own siblings, by text introducing an
+/// example's result (e.g. "...your puzzle answer was `42`."). This lets us
+/// accept a value `is_plausible_answer` would otherwise reject (longer
+/// tokens, or ones containing whitespace) for the token the phrase actually
+/// refers to, rather than any token merely sharing a paragraph with one.
+fn nearby_answer_phrase(el: &ElementRef) -> bool {
+    let Some(parent) = el.parent().and_then(ElementRef::wrap) else {
+        return false;
+    };
+    let target = el.id();
+    let mut seen_phrase = false;
+    for child in parent.children() {
+        if child.id() == target {
+            return seen_phrase;
+        }
+        if let Some(text) = child.value().as_text() {
+            let lower = text.to_lowercase();
+            if ANSWER_PHRASES.iter().any(|phrase| lower.contains(phrase)) {
+                seen_phrase = true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_the_phrase_backed_token_over_an_earlier_intermediate_value() {
+        let html = r#"
+            <article class="day-desc">
+                <pre><code>1
+2
+3
+</code></pre>
+                <p>Along the way you might see <code>99</code>, but your puzzle answer was <code>42</code>.</p>
+            </article>
+        "#;
+
+        let tests = Client::extract_tests(html);
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].input, "1\n2\n3");
+        assert_eq!(tests[0].expected.as_deref(), Some("42"));
+        assert_eq!(tests[0].part, 1);
+    }
+
+    #[test]
+    fn falls_back_to_the_last_plausible_token_without_a_phrase() {
+        let html = r#"
+            <article class="day-desc">
+                <pre><code>1
+2
+3
+</code></pre>
+                <p>First you get <code>7</code>, then <code>42</code>.</p>
+            </article>
+        "#;
+
+        let tests = Client::extract_tests(html);
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].expected.as_deref(), Some("42"));
+    }
+
+    #[test]
+    fn leaves_expected_none_when_no_candidate_follows() {
+        let html = r#"
+            <article class="day-desc">
+                <pre><code>1
+2
+3
+</code></pre>
+                <p>No result is stated here.</p>
+            </article>
+        "#;
+
+        let tests = Client::extract_tests(html);
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].expected, None);
+    }
 }
@@ -0,0 +1,81 @@
+use std::{
+    io::Write,
+    process::{Command as Process, Stdio},
+    time::{Duration, Instant},
+};
+
+use anyhow::{ensure, Context, Result};
+
+use crate::{PuzzleId, TestCase};
+
+/// Default shell command used to run a user's solution, with `{year}`/`{day}`/`{part}`
+/// placeholders substituted before spawning.
+pub const DEFAULT_RUN_CMD: &str = "cargo run --release";
+
+/// The result of running a solution against a single [`TestCase`].
+pub struct TestResult {
+    pub part: u8,
+    pub input: String,
+    pub expected: Option<String>,
+    pub actual: String,
+    pub elapsed: Duration,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        self.expected
+            .as_deref()
+            .is_some_and(|e| e.trim() == self.actual.trim())
+    }
+}
+
+/// Substitute the `{year}`/`{day}`/`{part}` placeholders in a run command template.
+pub fn expand_cmd(cmd: &str, (year, day): &PuzzleId, part: u8) -> String {
+    cmd.replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+        .replace("{part}", &part.to_string())
+}
+
+/// Spawn the user's solution with `input` piped to stdin and capture stdout.
+pub fn run_solution(cmd: &str, id: &PuzzleId, part: u8, input: &str) -> Result<(String, Duration)> {
+    let cmd = expand_cmd(cmd, id, part);
+    ensure!(!cmd.trim().is_empty(), "empty run command");
+
+    let start = Instant::now();
+    let mut child = Process::new("sh")
+        .arg("-c")
+        .arg(&cmd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("spawn `{cmd}`"))?;
+
+    child
+        .stdin
+        .take()
+        .context("child stdin")?
+        .write_all(input.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    Ok((
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        start.elapsed(),
+    ))
+}
+
+/// Run a solution against every cached example for a puzzle.
+pub fn run_tests(cmd: &str, id: &PuzzleId, tests: &[TestCase]) -> Result<Vec<TestResult>> {
+    tests
+        .iter()
+        .map(|t| {
+            let (actual, elapsed) = run_solution(cmd, id, t.part, &t.input)?;
+            Ok(TestResult {
+                part: t.part,
+                input: t.input.clone(),
+                expected: t.expected.clone(),
+                actual,
+                elapsed,
+            })
+        })
+        .collect()
+}